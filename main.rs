@@ -1,27 +1,154 @@
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use std::{borrow::BorrowMut, error::Error, io};
+use std::{
+    borrow::BorrowMut,
+    collections::HashSet,
+    error::Error,
+    fs,
+    io,
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
 use tui::{
     backend::{Backend, CrosstermBackend},
     layout::{Constraint, Direction, Layout},
-    style::{Color, Style},
-    widgets::{Block, Borders, List, ListItem, ListState},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame, Terminal,
 };
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InputMode {
+    Normal,
+    Editing,
+}
+
+enum AppEvent {
+    Input(KeyEvent),
+    Tick,
+}
+
+fn spawn_input_thread() -> mpsc::Receiver<AppEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || loop {
+        if let Ok(Event::Key(key)) = event::read() {
+            if tx.send(AppEvent::Input(key)).is_err() {
+                return;
+            }
+        }
+    });
+    rx
+}
+
+#[derive(Clone)]
+struct Cell {
+    color: Color,
+    label: String,
+}
+
+impl Cell {
+    fn new() -> Cell {
+        Cell {
+            color: Color::Red,
+            label: String::from(" "),
+        }
+    }
+}
+
+const SAVE_PATH: &str = "grid.state";
+
+fn save_board(cols: &[ColumnState<Cell>]) -> io::Result<()> {
+    let rows = cols.get(0).map_or(0, |col| col.items.len());
+    let mut contents = format!("{}\t{}\n", rows, cols.len());
+    for col in cols.iter() {
+        for cell in col.items.iter() {
+            contents.push_str(color_name(cell.color));
+            contents.push('\t');
+            contents.push_str(&cell.label);
+            contents.push('\n');
+        }
+    }
+    fs::write(SAVE_PATH, contents)
+}
+
+fn load_board(cols: &mut [ColumnState<Cell>]) -> io::Result<()> {
+    let contents = fs::read_to_string(SAVE_PATH)?;
+    let mut lines = contents.lines();
+    let header = lines
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing save file header"))?;
+    let (saved_rows, saved_cols) = header
+        .split_once('\t')
+        .and_then(|(rows, cols)| Some((rows.parse::<usize>().ok()?, cols.parse::<usize>().ok()?)))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed save file header"))?;
+    let rows = cols.get(0).map_or(0, |col| col.items.len());
+    if saved_rows != rows || saved_cols != cols.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "save file does not match the configured board size",
+        ));
+    }
+    let cell_count = saved_rows * saved_cols;
+    let mut loaded = Vec::with_capacity(cell_count);
+    for _ in 0..cell_count {
+        let line = lines
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "truncated save file"))?;
+        let (color, label) = line
+            .split_once('\t')
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed save line"))?;
+        loaded.push(Cell {
+            color: color_from_name(color),
+            label: label.to_string(),
+        });
+    }
+    if lines.next().is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "save file does not match the configured board size",
+        ));
+    }
+    let mut loaded = loaded.into_iter();
+    for col in cols.iter_mut() {
+        for cell in col.items.iter_mut() {
+            *cell = loaded.next().expect("loaded exactly cell_count cells above");
+        }
+    }
+    Ok(())
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+        _ => "red",
+    }
+}
+
+fn color_from_name(name: &str) -> Color {
+    match name {
+        "white" => Color::White,
+        "black" => Color::Black,
+        _ => Color::Red,
+    }
+}
+
 struct ColumnState<T> {
-    items: [T; 3],
+    items: Vec<T>,
     state: ListState,
+    marked: HashSet<usize>,
 }
 
 impl<T> ColumnState<T> {
-    fn new(items: [T; 3]) -> ColumnState<T> {
+    fn new(items: Vec<T>) -> ColumnState<T> {
         ColumnState {
             items,
             state: ListState::default(),
+            marked: HashSet::new(),
         }
     }
 
@@ -31,7 +158,7 @@ impl<T> ColumnState<T> {
                 if i < self.items.len() - 1 {
                     i + 1
                 } else {
-                    i
+                    0
                 }
             }
             None => row,
@@ -45,7 +172,7 @@ impl<T> ColumnState<T> {
                 if i > 0 {
                     i - 1
                 } else {
-                    i
+                    self.items.len() - 1
                 }
             }
             None => row,
@@ -56,16 +183,51 @@ impl<T> ColumnState<T> {
     pub fn return_selected(&mut self) -> usize {
         let _i = match self.state.selected() {
             Some(_i) => return _i,
-            None => return 9,
+            None => return self.items.len(),
         };
     }
 
     pub fn unselect(&mut self) {
         self.state.select(None);
     }
+
+    pub fn toggle_mark(&mut self) {
+        if let Some(i) = self.state.selected() {
+            if !self.marked.remove(&i) {
+                self.marked.insert(i);
+            }
+        }
+    }
+}
+
+const MAX_COLS: usize = 100;
+
+struct Config {
+    rows: usize,
+    cols: usize,
+}
+
+impl Config {
+    fn from_args() -> Config {
+        let args: Vec<String> = std::env::args().collect();
+        let mut config = Config { rows: 3, cols: 3 };
+        let mut i = 1;
+        while i < args.len() {
+            let value = args.get(i + 1).and_then(|v| v.parse().ok());
+            match (args[i].as_str(), value) {
+                ("--rows", Some(rows)) => config.rows = usize::max(rows, 1),
+                ("--cols", Some(cols)) => config.cols = cols.clamp(1, MAX_COLS),
+                _ => {}
+            }
+            i += 2;
+        }
+        config
+    }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let config = Config::from_args();
+
     // setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -73,61 +235,94 @@ fn main() -> Result<(), Box<dyn Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut left_column = ColumnState::new([
-        ListItem::new(" ").style(Style::default().bg(Color::Red)),
-        ListItem::new(" ").style(Style::default().bg(Color::Red)),
-        ListItem::new(" ").style(Style::default().bg(Color::Red)),
-    ]);
-
-    let mut central_column = ColumnState::new([
-        ListItem::new(" ").style(Style::default().bg(Color::Red)),
-        ListItem::new(" ").style(Style::default().bg(Color::Red)),
-        ListItem::new(" ").style(Style::default().bg(Color::Red)),
-    ]);
-
-    let mut right_column = ColumnState::new([
-        ListItem::new(" ").style(Style::default().bg(Color::Red)),
-        ListItem::new(" ").style(Style::default().bg(Color::Red)),
-        ListItem::new(" ").style(Style::default().bg(Color::Red)),
-    ]);
+    let mut columns: Vec<ColumnState<Cell>> = (0..config.cols)
+        .map(|_| ColumnState::new(vec![Cell::new(); config.rows]))
+        .collect();
 
     let mut column_number: usize = 0;
     let mut row: usize = 0;
+    let mut input_mode = InputMode::Normal;
+    let mut input = String::new();
+    let mut status = String::new();
+    let tick_rate = Duration::from_millis(250);
+    let rx = spawn_input_thread();
     loop {
-        terminal.draw(|f| {
-            ui(
-                f,
-                &mut [&mut left_column, &mut central_column, &mut right_column],
-            )
-        })?;
-        let mut columns = [&mut left_column, &mut central_column, &mut right_column];
-        if let Event::Key(key) = event::read()? {
-            match key.code {
-                KeyCode::Char('q') => break,
-                KeyCode::Down => {
-                    columns[column_number].next(row);
-                    row = columns[column_number].return_selected();
-                }
-                KeyCode::Up => {
-                    columns[column_number].previous(row);
-                    row = columns[column_number].return_selected();
-                }
-                KeyCode::Right => {
-                    if column_number < 2 {
+        terminal.draw(|f| ui(f, &mut columns, input_mode, &input, &status))?;
+        let event = match rx.recv_timeout(tick_rate) {
+            Ok(event) => event,
+            Err(mpsc::RecvTimeoutError::Timeout) => AppEvent::Tick,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+        if let AppEvent::Input(key) = event {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match input_mode {
+                InputMode::Normal => match key.code {
+                    KeyCode::Char('q') => break,
+                    KeyCode::Down => {
+                        columns[column_number].next(row);
+                        row = columns[column_number].return_selected();
+                    }
+                    KeyCode::Up => {
+                        columns[column_number].previous(row);
+                        row = columns[column_number].return_selected();
+                    }
+                    KeyCode::Right => {
                         columns[column_number].unselect();
-                        column_number += 1;
+                        column_number = if column_number < columns.len() - 1 {
+                            column_number + 1
+                        } else {
+                            0
+                        };
                         columns[column_number].next(row);
                     }
-                }
-                KeyCode::Left => {
-                    if column_number > 0 {
+                    KeyCode::Left => {
                         columns[column_number].unselect();
-                        column_number -= 1;
+                        column_number = if column_number > 0 {
+                            column_number - 1
+                        } else {
+                            columns.len() - 1
+                        };
                         columns[column_number].next(row);
                     }
-                }
-                KeyCode::Char('w') => toggle_white(&mut columns),
-                _ => {}
+                    KeyCode::Char('w') => toggle_white(&mut columns),
+                    KeyCode::Char('b') => toggle_black(&mut columns),
+                    KeyCode::Char(' ') => columns[column_number].toggle_mark(),
+                    KeyCode::Char('i') | KeyCode::Enter => {
+                        input.clear();
+                        input_mode = InputMode::Editing;
+                    }
+                    KeyCode::Char('s') => {
+                        status = match save_board(&columns) {
+                            Ok(()) => "Saved grid".to_string(),
+                            Err(e) => format!("Save failed: {}", e),
+                        };
+                    }
+                    KeyCode::Char('r') => {
+                        status = match load_board(&mut columns) {
+                            Ok(()) => "Reloaded grid".to_string(),
+                            Err(e) => format!("Reload failed: {}", e),
+                        };
+                    }
+                    _ => {}
+                },
+                InputMode::Editing => match key.code {
+                    KeyCode::Enter => {
+                        columns[column_number].items[row].label = input.clone();
+                        input.clear();
+                        input_mode = InputMode::Normal;
+                    }
+                    KeyCode::Esc => {
+                        input.clear();
+                        input_mode = InputMode::Normal;
+                    }
+                    KeyCode::Backspace => {
+                        input.pop();
+                    }
+                    KeyCode::Char(c) => input.push(c),
+                    _ => {}
+                },
             }
         }
     }
@@ -135,7 +330,13 @@ fn main() -> Result<(), Box<dyn Error>> {
     return Ok(());
 }
 
-fn ui<B: Backend>(f: &mut Frame<B>, cols: &mut [&mut ColumnState<ListItem>; 3]) {
+fn ui<B: Backend>(
+    f: &mut Frame<B>,
+    cols: &mut [ColumnState<Cell>],
+    input_mode: InputMode,
+    input: &str,
+    status: &str,
+) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .margin(1)
@@ -147,53 +348,86 @@ fn ui<B: Backend>(f: &mut Frame<B>, cols: &mut [&mut ColumnState<ListItem>; 3])
         .split(chunks[1]);
     let grid_layout = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
+        .constraints(vec![
+            Constraint::Percentage((100 / cols.len()) as u16);
+            cols.len()
         ])
         .split(chunks[0]);
-    let list_left = List::new(cols[0].items.clone())
-        .style(Style::default().fg(Color::Yellow))
-        .highlight_style(Style::default().bg(Color::DarkGray));
-    f.render_stateful_widget(list_left, grid_layout[0], &mut cols[0].state);
-
-    let list_center = List::new(cols[1].items.clone())
-        .style(Style::default().fg(Color::Yellow))
-        .highlight_style(Style::default().bg(Color::DarkGray));
-    f.render_stateful_widget(list_center, grid_layout[1], &mut cols[1].state);
-
-    let list_right = List::new(cols[2].items.clone())
-        .style(Style::default().fg(Color::Yellow))
-        .highlight_style(Style::default().bg(Color::DarkGray));
-    f.render_stateful_widget(list_right, grid_layout[2], &mut cols[2].state);
-
-    let commands_list = [
-        ListItem::new("Use arrows to select slot"),
-        ListItem::new("Press 'w' to select white"),
-        ListItem::new("Press 'b' to select black"),
-        ListItem::new("Press 'q' to exit"),
-    ];
-    let commands = List::new(commands_list)
-        .block(
-            Block::default()
-                .title("Available Commands")
-                .borders(Borders::ALL),
-        )
-        .style(Style::default().fg(Color::Yellow));
-    f.render_widget(commands, smaller_chunks[0]);
-}
-
-fn toggle_white(cols: &mut [&mut ColumnState<ListItem>; 3]) {
-    for col in cols.into_iter() {
-        let row = col.return_selected();
-        if row != 9 {
-            println!("{}", row);
-            col.items[row].style(Style::default().bg(Color::White));
+    for (i, col) in cols.iter_mut().enumerate() {
+        let list = List::new(marked_items(col))
+            .style(Style::default().fg(Color::Yellow))
+            .highlight_style(Style::default().bg(Color::DarkGray));
+        f.render_stateful_widget(list, grid_layout[i], &mut col.state);
+    }
+
+    match input_mode {
+        InputMode::Normal => {
+            let mut commands_list = vec![
+                ListItem::new("Use arrows to select slot"),
+                ListItem::new("Press 'space' to mark/unmark a slot"),
+                ListItem::new("Press 'w' to paint marked slots white"),
+                ListItem::new("Press 'b' to paint marked slots black"),
+                ListItem::new("Press 'i' or Enter to edit the focused slot"),
+                ListItem::new("Press 's' to save, 'r' to reload"),
+                ListItem::new("Press 'q' to exit"),
+            ];
+            if !status.is_empty() {
+                commands_list
+                    .push(ListItem::new(status.to_string()).style(Style::default().fg(Color::Green)));
+            }
+            let commands = List::new(commands_list)
+                .block(
+                    Block::default()
+                        .title("Available Commands")
+                        .borders(Borders::ALL),
+                )
+                .style(Style::default().fg(Color::Yellow));
+            f.render_widget(commands, smaller_chunks[0]);
+        }
+        InputMode::Editing => {
+            let input_box = Paragraph::new(input)
+                .style(Style::default().fg(Color::Yellow))
+                .block(
+                    Block::default()
+                        .title("Editing slot (Enter to confirm, Esc to cancel)")
+                        .borders(Borders::ALL),
+                );
+            f.render_widget(input_box, smaller_chunks[0]);
         }
     }
 }
 
+fn marked_items(col: &ColumnState<Cell>) -> Vec<ListItem<'static>> {
+    col.items
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            let style = if col.marked.contains(&i) {
+                Style::default().bg(Color::Cyan).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().bg(cell.color)
+            };
+            ListItem::new(cell.label.clone()).style(style)
+        })
+        .collect()
+}
+
+fn apply_color_to_marked(cols: &mut [ColumnState<Cell>], color: Color) {
+    for col in cols.iter_mut() {
+        for row in col.marked.drain() {
+            col.items[row].color = color;
+        }
+    }
+}
+
+fn toggle_white(cols: &mut [ColumnState<Cell>]) {
+    apply_color_to_marked(cols, Color::White);
+}
+
+fn toggle_black(cols: &mut [ColumnState<Cell>]) {
+    apply_color_to_marked(cols, Color::Black);
+}
+
 fn exit(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), io::Error> {
     disable_raw_mode()?;
     execute!(